@@ -0,0 +1,50 @@
+//! Benchmarks for `vint64::decode`, using a distribution skewed toward
+//! small integers (the common case for real-world payloads) to guard
+//! against regressions in the fast-path decoder.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// A tiny xorshift PRNG so the benchmark input is deterministic without
+/// pulling in a `rand` dependency.
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Generate values skewed toward small integers: roughly 80% fit in one
+/// byte, 15% in two or three, and the remaining 5% are spread across the
+/// full `u64` range.
+fn skewed_small_values(count: usize) -> Vec<u64> {
+    let mut rng = Xorshift(0x2545_f491_4f6c_dd1d);
+
+    (0..count)
+        .map(|_| match rng.next() % 100 {
+            0..=79 => rng.next() % (1 << 7),
+            80..=94 => rng.next() % (1 << 21),
+            _ => rng.next(),
+        })
+        .collect()
+}
+
+fn decode_benchmark(c: &mut Criterion) {
+    let values = skewed_small_values(10_000);
+    let encoded: Vec<_> = values.iter().map(|&v| vint64::encode(v)).collect();
+
+    c.bench_function("decode_skewed_small", |b| {
+        b.iter(|| {
+            for value in &encoded {
+                let mut bytes = value.as_ref();
+                criterion::black_box(vint64::decode(&mut bytes).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, decode_benchmark);
+criterion_main!(benches);
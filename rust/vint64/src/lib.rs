@@ -0,0 +1,364 @@
+//! `vint64`: a simple and efficient variable-length integer encoding.
+//!
+//! This crate implements a variant of the [LEB128] encoding in which the
+//! number of bytes used to represent the integer is determined by the
+//! number of trailing zero bits in the first byte:
+//!
+//! - If the first byte is non-zero, the number of trailing zero bits in
+//!   that byte (plus one) gives the total length of the encoding in bytes,
+//!   and the bits of the first byte above its lowest set bit hold the
+//!   least-significant bits of the value, with any further bytes holding
+//!   the rest of the value in little-endian order.
+//! - If the first byte is zero, the encoding is 9 bytes long and the
+//!   remaining 8 bytes hold the full 64-bit value in little-endian order.
+//!
+//! This scheme is prefix-free and self-describing: the length of an
+//! encoded integer can always be determined from its first byte alone.
+//!
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+
+#![no_std]
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Maximum length of an encoded `vint64` in bytes.
+pub const MAX_BYTES: usize = 9;
+
+/// Result type for this crate.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Errors that can occur while encoding or decoding a `vint64`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Not enough bytes were available to decode a complete value.
+    Truncated,
+
+    /// The encoded value used more bytes than necessary to represent it
+    /// (i.e. it was not in canonical form).
+    Noncanonical,
+
+    /// The destination buffer passed to [`encode_to_slice`] was too small
+    /// to hold the encoded value.
+    BufferTooSmall,
+
+    /// A frame decoded by [`read_length_delimited`] declared a length
+    /// exceeding the caller-supplied maximum.
+    FrameTooLong,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Truncated => f.write_str("truncated vint64 value"),
+            Error::Noncanonical => f.write_str("noncanonical vint64 encoding"),
+            Error::BufferTooSmall => f.write_str("buffer too small to hold encoded vint64"),
+            Error::FrameTooLong => f.write_str("length-delimited frame exceeds maximum length"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Compute the number of bytes required to encode `value`.
+pub fn encoded_len(value: u64) -> usize {
+    // The number of bits needed to represent `value`, plus one bit per
+    // byte consumed by the unary length prefix, must fit in the encoding.
+    let bits = 64 - value.leading_zeros() as usize;
+
+    for len in 1..MAX_BYTES {
+        if bits + len <= len * 8 {
+            return len;
+        }
+    }
+
+    MAX_BYTES
+}
+
+/// Compute the length (in bytes) of an encoded `vint64` from its first byte.
+pub fn decoded_len(byte: u8) -> usize {
+    // Numbers of trailing zeros, plus one. A first byte of zero indicates
+    // the maximum-length (9-byte) encoding.
+    byte.trailing_zeros() as usize + 1
+}
+
+/// An encoded `vint64`, stored inline without any heap allocation.
+#[derive(Copy, Clone, Debug)]
+pub struct Encoded([u8; MAX_BYTES], u8);
+
+impl AsRef<[u8]> for Encoded {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..self.1 as usize]
+    }
+}
+
+impl Encoded {
+    /// Get the bytes of this encoded value as a slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    /// Get the number of bytes in this encoded value.
+    pub fn len(&self) -> usize {
+        self.1 as usize
+    }
+
+    /// Is this encoding empty? (never true; provided for API completeness)
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Encode a `u64` as a `vint64`.
+pub fn encode(value: u64) -> Encoded {
+    let len = encoded_len(value);
+    let mut bytes = [0u8; MAX_BYTES];
+
+    if len < MAX_BYTES {
+        let encoded_value = (value << 1 | 1) << (len - 1);
+        bytes[..8].copy_from_slice(&encoded_value.to_le_bytes());
+    } else {
+        bytes[1..].copy_from_slice(&value.to_le_bytes());
+    }
+
+    Encoded(bytes, len as u8)
+}
+
+/// Decode a `vint64` from the front of a byte slice, advancing the slice
+/// past the bytes that were consumed.
+///
+/// Most real-world payloads are dominated by 1-, 2- and 3-byte values, so
+/// those lengths are decoded inline here; anything longer falls back to
+/// [`decode_slow`], which is marked `#[cold]` to keep it out of this
+/// function's instruction cache footprint.
+pub fn decode(bytes: &mut &[u8]) -> Result<u64> {
+    let first = *bytes.first().ok_or(Error::Truncated)?;
+
+    if first & 0b1 == 0b1 {
+        // 1-byte encoding: the 7 value bits are the high bits of `first`.
+        // Every value in range is canonical, since there's no shorter
+        // encoding to compare against.
+        *bytes = &bytes[1..];
+        return Ok((first >> 1) as u64);
+    }
+
+    if first & 0b11 == 0b10 {
+        // 2-byte encoding.
+        if bytes.len() < 2 {
+            return Err(Error::Truncated);
+        }
+
+        let value = (u16::from_le_bytes([bytes[0], bytes[1]]) >> 2) as u64;
+
+        if value < 0x80 {
+            return Err(Error::Noncanonical);
+        }
+
+        *bytes = &bytes[2..];
+        return Ok(value);
+    }
+
+    if first & 0b111 == 0b100 {
+        // 3-byte encoding.
+        if bytes.len() < 3 {
+            return Err(Error::Truncated);
+        }
+
+        let value = (u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) >> 3) as u64;
+
+        if value < 0x4000 {
+            return Err(Error::Noncanonical);
+        }
+
+        *bytes = &bytes[3..];
+        return Ok(value);
+    }
+
+    decode_slow(bytes, first)
+}
+
+/// General-case decoding for 4-byte and longer `vint64` encodings.
+///
+/// This is the out-of-line slow path for [`decode`]; it is rarely taken
+/// in practice, so it's marked `#[cold]` to keep the common short-value
+/// cases in `decode` fast.
+#[cold]
+fn decode_slow(bytes: &mut &[u8], first: u8) -> Result<u64> {
+    let len = decoded_len(first);
+
+    if bytes.len() < len {
+        return Err(Error::Truncated);
+    }
+
+    let value = assemble(&bytes[..len], len)?;
+    *bytes = &bytes[len..];
+    Ok(value)
+}
+
+/// Reassemble the value from its `len`-byte encoding, rejecting
+/// non-canonical (overlong) encodings.
+///
+/// `bytes` must be exactly `len` bytes long.
+fn assemble(bytes: &[u8], len: usize) -> Result<u64> {
+    let value = if len < MAX_BYTES {
+        let mut buf = [0u8; 8];
+        buf[..len].copy_from_slice(bytes);
+        u64::from_le_bytes(buf) >> len
+    } else {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&bytes[1..MAX_BYTES]);
+        u64::from_le_bytes(buf)
+    };
+
+    if encoded_len(value) != len {
+        return Err(Error::Noncanonical);
+    }
+
+    Ok(value)
+}
+
+/// Decode a `vint64` by reading it directly from a [`std::io::Read`]
+/// implementor.
+///
+/// This reads the length-prefix byte first, then issues a single
+/// `read_exact` for exactly the number of continuation bytes the prefix
+/// indicates, so callers can pull a varint off a socket or file without
+/// buffering the whole stream or guessing how many bytes to pre-read.
+///
+/// EOF while reading the prefix byte, or partway through the
+/// continuation bytes, surfaces as the usual `ErrorKind::UnexpectedEof`;
+/// a malformed (non-canonical) encoding is reported separately as
+/// `ErrorKind::InvalidData`.
+#[cfg(feature = "std")]
+pub fn decode_from_reader(r: &mut impl std::io::Read) -> std::io::Result<u64> {
+    let mut buf = [0u8; MAX_BYTES];
+    r.read_exact(&mut buf[..1])?;
+
+    let len = decoded_len(buf[0]);
+    r.read_exact(&mut buf[1..len])?;
+
+    assemble(&buf[..len], len).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Encode `value` and append the resulting bytes to `out`.
+///
+/// Callers never handle the [`Encoded`] value that `encode` returns (it is
+/// built on the stack internally and copied into `out`), so they can
+/// serialize many fields directly into one growing
+/// `Vec<u8>` (or any other `Extend<u8>` implementor) without per-field
+/// allocations.
+pub fn encode_into(value: u64, out: &mut impl Extend<u8>) {
+    out.extend(encode(value).as_ref().iter().copied());
+}
+
+/// Encode `value` into the front of `out`, returning the number of bytes
+/// written.
+///
+/// Returns [`Error::BufferTooSmall`] if `out` is not large enough to hold
+/// the encoded value; callers can use [`encoded_len`] to size `out` ahead
+/// of time.
+pub fn encode_to_slice(value: u64, out: &mut [u8]) -> Result<usize> {
+    let encoded = encode(value);
+    let bytes = encoded.as_ref();
+
+    if out.len() < bytes.len() {
+        return Err(Error::BufferTooSmall);
+    }
+
+    out[..bytes.len()].copy_from_slice(bytes);
+    Ok(bytes.len())
+}
+
+/// Encode a signed `i64` as a `vint64`.
+///
+/// Values are zigzag-transcoded before being handed to [`encode`], so that
+/// small-magnitude negative numbers remain as compact as their positive
+/// counterparts instead of encoding as the maximum-length form.
+pub fn encode_signed(value: i64) -> Encoded {
+    encode(zigzag_encode(value))
+}
+
+/// Decode a zigzag-encoded signed `i64` `vint64` from the front of a byte
+/// slice, advancing the slice past the bytes that were consumed.
+pub fn decode_signed(bytes: &mut &[u8]) -> Result<i64> {
+    decode(bytes).map(zigzag_decode)
+}
+
+/// Write `payload` to `out` as a length-delimited frame: a `vint64`
+/// encoding of `payload.len()`, followed by `payload` itself.
+///
+/// This gives veriform values a self-describing boundary when
+/// concatenated on a stream, so a reader can pull one frame off the front
+/// without knowing where the next one starts ahead of time.
+pub fn write_length_delimited(out: &mut impl Extend<u8>, payload: &[u8]) {
+    encode_into(payload.len() as u64, out);
+    out.extend(payload.iter().copied());
+}
+
+/// Read a length-delimited frame from the front of a byte slice,
+/// advancing the slice past the bytes that were consumed, and return a
+/// borrowed slice of exactly the frame's payload.
+///
+/// The declared length is validated against both `max_len` and the
+/// remaining buffer, so a corrupt or hostile prefix can't trigger a huge
+/// allocation or read; `max_len` bounds the former and `bytes.len()`
+/// naturally bounds the latter, since the returned slice only ever
+/// borrows from what's already present.
+pub fn read_length_delimited<'a>(bytes: &mut &'a [u8], max_len: usize) -> Result<&'a [u8]> {
+    let len = decode(bytes)?;
+    let len = usize::try_from(len).map_err(|_| Error::FrameTooLong)?;
+
+    if len > max_len {
+        return Err(Error::FrameTooLong);
+    }
+
+    if bytes.len() < len {
+        return Err(Error::Truncated);
+    }
+
+    let (payload, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(payload)
+}
+
+/// Map a signed integer to an unsigned one, encoding the sign in the
+/// low-order bit so that small-magnitude values (positive or negative)
+/// both map to small unsigned values.
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of [`zigzag_encode`].
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_len_matches_decoded_len() {
+        for &value in &[0u64, 1, 127, 128, u64::MAX / 2, u64::MAX] {
+            let encoded = encode(value);
+            assert_eq!(encoded.len(), decoded_len(encoded.as_ref()[0]));
+        }
+    }
+
+    #[test]
+    fn decode_rejects_noncanonical_short_encodings() {
+        // 2-byte prefix tag encoding a value that fits in 1 byte.
+        let mut bytes: &[u8] = &[0b10, 0];
+        assert_eq!(decode(&mut bytes), Err(Error::Noncanonical));
+
+        // 3-byte prefix tag encoding a value that fits in 2 bytes.
+        let mut bytes: &[u8] = &[0b100, 0, 0];
+        assert_eq!(decode(&mut bytes), Err(Error::Noncanonical));
+    }
+}
@@ -1,6 +1,6 @@
 #[test]
 fn roundtrip_u32() {
-    for i in 0..=u32::max_value() {
+    for i in 0..=u32::MAX {
         let encoded = vint64::encode(i as u64);
         let out = vint64::decode(&mut encoded.as_ref()).unwrap_or_else(|e| {
             panic!(
@@ -13,3 +13,167 @@ fn roundtrip_u32() {
         assert_eq!(out, i);
     }
 }
+
+#[test]
+fn roundtrip_i32() {
+    for i in i32::MIN..=i32::MAX {
+        let encoded = vint64::encode_signed(i as i64);
+        let out = vint64::decode_signed(&mut encoded.as_ref()).unwrap_or_else(|e| {
+            panic!(
+                "error while decoding {}: {:?} (bytes: {:x?})",
+                i,
+                e,
+                encoded.as_ref()
+            );
+        }) as i32;
+        assert_eq!(out, i);
+    }
+}
+
+#[test]
+fn roundtrip_i64_boundaries() {
+    for &i in &[i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX] {
+        let encoded = vint64::encode_signed(i);
+        let out = vint64::decode_signed(&mut encoded.as_ref()).unwrap_or_else(|e| {
+            panic!(
+                "error while decoding {}: {:?} (bytes: {:x?})",
+                i,
+                e,
+                encoded.as_ref()
+            );
+        });
+        assert_eq!(out, i);
+    }
+}
+
+/// Values at and around each length-prefix transition within `u32`'s
+/// range, plus a modest deterministic random sample, for tests that only
+/// need to confirm one code path agrees with another (as opposed to
+/// `roundtrip_u32`/`roundtrip_i32`, which brute-force the entire range to
+/// validate the core encode/decode logic itself).
+fn boundary_and_random_u32_samples() -> Vec<u32> {
+    let mut values = vec![
+        0,
+        1,
+        2,
+        126,
+        127,
+        128,
+        129,
+        16_382,
+        16_383,
+        16_384,
+        16_385,
+        2_097_150,
+        2_097_151,
+        2_097_152,
+        2_097_153,
+        268_435_454,
+        268_435_455,
+        268_435_456,
+        268_435_457,
+        u32::MAX - 1,
+        u32::MAX,
+    ];
+
+    // A tiny xorshift PRNG so the sample is deterministic without pulling
+    // in a `rand` dependency.
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    for _ in 0..1_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        values.push((state % (u32::MAX as u64 + 1)) as u32);
+    }
+
+    values
+}
+
+#[test]
+fn encode_into_matches_encode() {
+    for i in boundary_and_random_u32_samples() {
+        let value = i as u64;
+        let expected = vint64::encode(value);
+
+        let mut into_buf = Vec::new();
+        vint64::encode_into(value, &mut into_buf);
+        assert_eq!(into_buf, expected.as_ref());
+
+        let mut slice_buf = [0u8; vint64::MAX_BYTES];
+        let len = vint64::encode_to_slice(value, &mut slice_buf).unwrap();
+        assert_eq!(&slice_buf[..len], expected.as_ref());
+    }
+}
+
+#[test]
+fn encode_to_slice_rejects_too_small_buffer() {
+    let mut buf = [0u8; 1];
+    let err = vint64::encode_to_slice(u64::MAX, &mut buf).unwrap_err();
+    assert_eq!(err, vint64::Error::BufferTooSmall);
+}
+
+#[test]
+fn decode_from_reader_matches_decode() {
+    for i in boundary_and_random_u32_samples() {
+        let value = i as u64;
+        let encoded = vint64::encode(value);
+
+        let mut reader = std::io::Cursor::new(encoded.as_ref());
+        let out = vint64::decode_from_reader(&mut reader).unwrap();
+        assert_eq!(out, value);
+    }
+}
+
+#[test]
+fn decode_from_reader_reports_truncated_eof() {
+    // A 2-byte prefix with no continuation byte available.
+    let mut reader = std::io::Cursor::new(&[0b10][..]);
+    let err = vint64::decode_from_reader(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn decode_from_reader_reports_noncanonical_as_invalid_data() {
+    // A 2-byte prefix encoding a value that fits in 1 byte.
+    let mut reader = std::io::Cursor::new(&[0b10, 0][..]);
+    let err = vint64::decode_from_reader(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn roundtrip_length_delimited_frames() {
+    let payloads: &[&[u8]] = &[b"", b"a", b"hello, veriform", &[0u8; 300]];
+
+    let mut buf = Vec::new();
+    for payload in payloads {
+        vint64::write_length_delimited(&mut buf, payload);
+    }
+
+    let mut remaining = buf.as_slice();
+    for payload in payloads {
+        let frame = vint64::read_length_delimited(&mut remaining, 1024).unwrap();
+        assert_eq!(frame, *payload);
+    }
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn read_length_delimited_rejects_frame_over_max_len() {
+    let mut buf = Vec::new();
+    vint64::write_length_delimited(&mut buf, &[0u8; 100]);
+
+    let mut remaining = buf.as_slice();
+    let err = vint64::read_length_delimited(&mut remaining, 50).unwrap_err();
+    assert_eq!(err, vint64::Error::FrameTooLong);
+}
+
+#[test]
+fn read_length_delimited_rejects_truncated_payload() {
+    let mut buf = Vec::new();
+    vint64::write_length_delimited(&mut buf, &[0u8; 100]);
+    buf.truncate(buf.len() - 1);
+
+    let mut remaining = buf.as_slice();
+    let err = vint64::read_length_delimited(&mut remaining, 1024).unwrap_err();
+    assert_eq!(err, vint64::Error::Truncated);
+}